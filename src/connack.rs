@@ -0,0 +1,194 @@
+use crate::properties::{parse_properties, Properties};
+use crate::{Error, Result, Status};
+
+/// The outcome of a connection attempt, reported in CONNACK (section 3.2.2.3).
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ConnectReturnCode {
+    Accepted,
+    UnacceptableProtocolVersion,
+    IdentifierRejected,
+    ServerUnavailable,
+    BadUserNameOrPassword,
+    NotAuthorized,
+}
+
+impl ConnectReturnCode {
+    pub fn from_u8(code: u8) -> Result<ConnectReturnCode> {
+        match code {
+            0 => Ok(ConnectReturnCode::Accepted),
+            1 => Ok(ConnectReturnCode::UnacceptableProtocolVersion),
+            2 => Ok(ConnectReturnCode::IdentifierRejected),
+            3 => Ok(ConnectReturnCode::ServerUnavailable),
+            4 => Ok(ConnectReturnCode::BadUserNameOrPassword),
+            5 => Ok(ConnectReturnCode::NotAuthorized),
+            _ => Err(Error::InvalidConnectReturnCode),
+        }
+    }
+
+    fn to_u8(self) -> u8 {
+        match self {
+            ConnectReturnCode::Accepted => 0,
+            ConnectReturnCode::UnacceptableProtocolVersion => 1,
+            ConnectReturnCode::IdentifierRejected => 2,
+            ConnectReturnCode::ServerUnavailable => 3,
+            ConnectReturnCode::BadUserNameOrPassword => 4,
+            ConnectReturnCode::NotAuthorized => 5,
+        }
+    }
+}
+
+/// A parsed CONNACK packet (MQTT 3.1.1/5.0, section 3.2).
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Connack<'a> {
+    pub session_present: bool,
+    pub return_code: ConnectReturnCode,
+
+    /// The raw MQTT 5 property block (length prefix included), or an empty
+    /// slice for an MQTT 3.1.1 CONNACK, which has no properties field at
+    /// all. Use [`Connack::properties`] to read it.
+    pub properties: &'a [u8],
+}
+
+impl<'a> Connack<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<Status<(Connack<'a>, usize)>> {
+        if bytes.len() < 2 {
+            return Err(Error::MalformedPacket);
+        }
+
+        // MQTT-3.2.2-1: bits 7-1 of the Connect Acknowledge Flags are
+        // reserved and must be 0.
+        if bytes[0] & 0xFE != 0 {
+            return Err(Error::InvalidConnackFlags);
+        }
+
+        let session_present = bytes[0] & 0x01 != 0;
+        let return_code = ConnectReturnCode::from_u8(bytes[1])?;
+        let properties = &bytes[2..];
+
+        Ok(Status::Complete((
+            Connack {
+                session_present,
+                return_code,
+                properties,
+            },
+            bytes.len(),
+        )))
+    }
+
+    /// Parses [`Connack::properties`]' raw bytes into the MQTT 5 property
+    /// block they hold, or an empty one for an MQTT 3.1.1 CONNACK.
+    pub fn properties(&self) -> Result<Properties<'a>> {
+        if self.properties.is_empty() {
+            return Ok(Properties::empty());
+        }
+
+        let (properties, consumed) = complete_or_err!(parse_properties(self.properties)?);
+        if consumed != self.properties.len() {
+            return Err(Error::InvalidProperty);
+        }
+
+        Ok(properties)
+    }
+
+    /// Encodes the CONNACK variable header, the mirror image of
+    /// [`Connack::parse`].
+    pub fn encode(&self, buf: &mut [u8]) -> Result<Status<usize>> {
+        if buf.len() < 2 + self.properties.len() {
+            return Ok(Status::Partial);
+        }
+
+        buf[0] = self.session_present as u8;
+        buf[1] = self.return_code.to_u8();
+        buf[2..2 + self.properties.len()].copy_from_slice(self.properties);
+
+        Ok(Status::Complete(2 + self.properties.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_buffer() {
+        assert_eq!(Err(Error::MalformedPacket), Connack::parse(&[]));
+        assert_eq!(Err(Error::MalformedPacket), Connack::parse(&[0]));
+    }
+
+    #[test]
+    fn accepted() {
+        let (connack, consumed) = Connack::parse(&[0x01, 0x00]).unwrap().unwrap();
+        assert!(connack.session_present);
+        assert_eq!(ConnectReturnCode::Accepted, connack.return_code);
+        assert_eq!(2, consumed);
+    }
+
+    #[test]
+    fn reserved_flags_set() {
+        assert_eq!(Err(Error::InvalidConnackFlags), Connack::parse(&[0x02, 0x00]));
+    }
+
+    #[test]
+    fn invalid_return_code() {
+        assert_eq!(
+            Err(Error::InvalidConnectReturnCode),
+            Connack::parse(&[0x00, 0x06])
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let connack = Connack {
+            session_present: true,
+            return_code: ConnectReturnCode::NotAuthorized,
+            properties: &[],
+        };
+
+        let mut buf = [0u8; 2];
+        let written = connack.encode(&mut buf).unwrap().unwrap();
+        let (parsed, consumed) = Connack::parse(&buf[..written]).unwrap().unwrap();
+
+        assert_eq!(connack, parsed);
+        assert_eq!(written, consumed);
+        assert_eq!(0, parsed.properties().unwrap().count());
+    }
+
+    #[test]
+    fn round_trip_with_properties() {
+        let properties = vec![0x24, 0x01]; // Maximum QoS
+
+        let mut buf = Vec::new();
+        buf.push(properties.len() as u8);
+        buf.extend_from_slice(&properties);
+
+        let connack = Connack {
+            session_present: false,
+            return_code: ConnectReturnCode::Accepted,
+            properties: &buf,
+        };
+
+        let mut encoded = [0u8; 8];
+        let written = connack.encode(&mut encoded).unwrap().unwrap();
+        let (parsed, consumed) = Connack::parse(&encoded[..written]).unwrap().unwrap();
+
+        assert_eq!(connack, parsed);
+        assert_eq!(written, consumed);
+
+        let parsed_properties: Vec<_> = parsed.properties().unwrap().collect::<Result<_>>().unwrap();
+        assert_eq!(vec![crate::properties::Property::MaximumQoS(1)], parsed_properties);
+    }
+
+    #[test]
+    fn properties_trailing_garbage() {
+        // Property Length says 2, but 4 more bytes follow in the body: the
+        // property block itself parses fine, so this can only be caught by
+        // checking that it consumed the whole `properties` slice.
+        let connack = Connack {
+            session_present: false,
+            return_code: ConnectReturnCode::Accepted,
+            properties: &[0x02, 0x24, 0x01, 0xDE, 0xAD, 0xBE, 0xEF],
+        };
+
+        assert_eq!(Err(Error::InvalidProperty), connack.properties());
+    }
+}
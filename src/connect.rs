@@ -0,0 +1,330 @@
+use byteorder::{BigEndian, ByteOrder};
+
+use crate::{
+    encode_len_prefixed_bytes, encode_string, parse_len_prefixed_bytes, parse_string, Error, QoS,
+    Result, Status,
+};
+
+/// The Last Will and Testament that a broker must publish on the client's
+/// behalf if the network connection is lost without a DISCONNECT.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Will<'a> {
+    pub qos: QoS,
+    pub retain: bool,
+    pub topic: &'a str,
+    pub message: &'a [u8],
+}
+
+/// A parsed CONNECT packet (MQTT 3.1.1, section 3.1).
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Connect<'a> {
+    pub protocol_level: u8,
+    pub clean_session: bool,
+    pub keep_alive: u16,
+    pub client_id: &'a str,
+    pub will: Option<Will<'a>>,
+    pub username: Option<&'a str>,
+    pub password: Option<&'a [u8]>,
+}
+
+impl<'a> Connect<'a> {
+    /// Parses a CONNECT packet's variable header and payload out of `bytes`
+    /// (the fixed header is expected to already have been consumed by the
+    /// caller). Returns the `Connect` together with the number of bytes
+    /// read.
+    pub fn parse(bytes: &'a [u8]) -> Result<Status<(Connect<'a>, usize)>> {
+        let mut pos = 0;
+
+        let (protocol_name, len) = complete_or_err!(parse_string(bytes)?);
+        pos += len;
+        if protocol_name != "MQTT" {
+            return Err(Error::InvalidProtocolName);
+        }
+
+        // Protocol Level (1 byte), Connect Flags (1 byte) and Keep Alive
+        // (2 bytes) follow the Protocol Name.
+        if bytes.len() < pos + 4 {
+            return Err(Error::MalformedPacket);
+        }
+
+        let protocol_level = bytes[pos];
+        pos += 1;
+
+        let flags = bytes[pos];
+        pos += 1;
+        // MQTT-3.1.2-3: the reserved flag bit must be 0.
+        if flags & 0x01 != 0 {
+            return Err(Error::InvalidConnectFlags);
+        }
+
+        let clean_session = flags & 0x02 != 0;
+        let will_flag = flags & 0x04 != 0;
+        let will_qos = QoS::from_u8((flags & 0x18) >> 3)?;
+        let will_retain = flags & 0x20 != 0;
+        let has_password = flags & 0x40 != 0;
+        let has_username = flags & 0x80 != 0;
+
+        // MQTT-3.1.2-11: Will QoS and Will Retain must be 0 when the Will
+        // Flag is 0.
+        if !will_flag && (will_qos != QoS::AtMostOnce || will_retain) {
+            return Err(Error::InvalidConnectFlags);
+        }
+        // MQTT-3.1.2-22: the Password Flag must be 0 if the User Name Flag
+        // is 0.
+        if has_password && !has_username {
+            return Err(Error::InvalidConnectFlags);
+        }
+
+        let keep_alive = BigEndian::read_u16(&bytes[pos..]);
+        pos += 2;
+
+        let (client_id, len) = complete_or_err!(parse_string(&bytes[pos..])?);
+        pos += len;
+
+        let will = if will_flag {
+            let (topic, len) = complete_or_err!(parse_string(&bytes[pos..])?);
+            pos += len;
+
+            let (message, len) = complete_or_err!(parse_len_prefixed_bytes(&bytes[pos..])?);
+            pos += len;
+
+            Some(Will {
+                qos: will_qos,
+                retain: will_retain,
+                topic,
+                message,
+            })
+        } else {
+            None
+        };
+
+        let username = if has_username {
+            let (username, len) = complete_or_err!(parse_string(&bytes[pos..])?);
+            pos += len;
+            Some(username)
+        } else {
+            None
+        };
+
+        let password = if has_password {
+            let (password, len) = complete_or_err!(parse_len_prefixed_bytes(&bytes[pos..])?);
+            pos += len;
+            Some(password)
+        } else {
+            None
+        };
+
+        Ok(Status::Complete((
+            Connect {
+                protocol_level,
+                clean_session,
+                keep_alive,
+                client_id,
+                will,
+                username,
+                password,
+            },
+            pos,
+        )))
+    }
+
+    /// Encodes the CONNECT variable header and payload, the mirror image
+    /// of [`Connect::parse`].
+    pub fn encode(&self, buf: &mut [u8]) -> Result<Status<usize>> {
+        let mut pos = complete!(encode_string("MQTT", buf)?);
+
+        if buf.len() < pos + 4 {
+            return Ok(Status::Partial);
+        }
+
+        buf[pos] = self.protocol_level;
+        pos += 1;
+
+        let mut flags = if self.clean_session { 0x02 } else { 0x00 };
+        if let Some(ref will) = self.will {
+            flags |= 0x04 | (will.qos.to_u8() << 3);
+            if will.retain {
+                flags |= 0x20;
+            }
+        }
+        if self.password.is_some() {
+            flags |= 0x40;
+        }
+        if self.username.is_some() {
+            flags |= 0x80;
+        }
+        buf[pos] = flags;
+        pos += 1;
+
+        BigEndian::write_u16(&mut buf[pos..], self.keep_alive);
+        pos += 2;
+
+        pos += complete!(encode_string(self.client_id, &mut buf[pos..])?);
+
+        if let Some(ref will) = self.will {
+            pos += complete!(encode_string(will.topic, &mut buf[pos..])?);
+            pos += complete!(encode_len_prefixed_bytes(will.message, &mut buf[pos..])?);
+        }
+
+        if let Some(username) = self.username {
+            pos += complete!(encode_string(username, &mut buf[pos..])?);
+        }
+
+        if let Some(password) = self.password {
+            pos += complete!(encode_len_prefixed_bytes(password, &mut buf[pos..])?);
+        }
+
+        Ok(Status::Complete(pos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Write};
+
+    use byteorder::WriteBytesExt;
+
+    fn write_str(buf: &mut Cursor<Vec<u8>>, s: &str) {
+        buf.write_u16::<BigEndian>(s.len() as u16).unwrap();
+        buf.write(s.as_bytes()).unwrap();
+    }
+
+    #[test]
+    fn small_buffer() {
+        assert_eq!(Err(Error::MalformedPacket), Connect::parse(&[]));
+    }
+
+    #[test]
+    fn minimal_connect() {
+        let mut buf = Cursor::new(Vec::new());
+        write_str(&mut buf, "MQTT");
+        buf.write_u8(4).unwrap(); // protocol level
+        buf.write_u8(0x02).unwrap(); // clean session, no will/username/password
+        buf.write_u16::<BigEndian>(60).unwrap(); // keep alive
+        write_str(&mut buf, "client-1");
+
+        let bytes = buf.get_ref().as_slice();
+        let (connect, consumed) = Connect::parse(bytes).unwrap().unwrap();
+
+        assert_eq!(4, connect.protocol_level);
+        assert!(connect.clean_session);
+        assert_eq!(60, connect.keep_alive);
+        assert_eq!("client-1", connect.client_id);
+        assert_eq!(None, connect.will);
+        assert_eq!(None, connect.username);
+        assert_eq!(None, connect.password);
+        assert_eq!(bytes.len(), consumed);
+    }
+
+    #[test]
+    fn connect_with_will_and_credentials() {
+        let mut buf = Cursor::new(Vec::new());
+        write_str(&mut buf, "MQTT");
+        buf.write_u8(4).unwrap();
+        buf.write_u8(0xEE).unwrap(); // username, password, will retain, will qos 1, will flag, clean session
+        buf.write_u16::<BigEndian>(30).unwrap();
+        write_str(&mut buf, "client-2");
+        write_str(&mut buf, "last/will/topic");
+        buf.write_u16::<BigEndian>(4).unwrap();
+        buf.write(b"bye!").unwrap();
+        write_str(&mut buf, "user");
+        buf.write_u16::<BigEndian>(4).unwrap();
+        buf.write(b"pass").unwrap();
+
+        let bytes = buf.get_ref().as_slice();
+        let (connect, consumed) = Connect::parse(bytes).unwrap().unwrap();
+
+        let will = connect.will.unwrap();
+        assert_eq!(QoS::AtLeastOnce, will.qos);
+        assert!(will.retain);
+        assert_eq!("last/will/topic", will.topic);
+        assert_eq!(b"bye!".as_ref(), will.message);
+        assert_eq!(Some("user"), connect.username);
+        assert_eq!(Some(b"pass".as_ref()), connect.password);
+        assert_eq!(bytes.len(), consumed);
+    }
+
+    #[test]
+    fn invalid_protocol_name() {
+        let mut buf = Cursor::new(Vec::new());
+        write_str(&mut buf, "MQTX");
+        buf.write_u8(4).unwrap();
+        buf.write_u8(0).unwrap();
+        buf.write_u16::<BigEndian>(0).unwrap();
+
+        assert_eq!(
+            Err(Error::InvalidProtocolName),
+            Connect::parse(buf.get_ref().as_slice())
+        );
+    }
+
+    #[test]
+    fn reserved_flag_set() {
+        let mut buf = Cursor::new(Vec::new());
+        write_str(&mut buf, "MQTT");
+        buf.write_u8(4).unwrap();
+        buf.write_u8(0x01).unwrap();
+        buf.write_u16::<BigEndian>(0).unwrap();
+
+        assert_eq!(
+            Err(Error::InvalidConnectFlags),
+            Connect::parse(buf.get_ref().as_slice())
+        );
+    }
+
+    #[test]
+    fn password_without_username() {
+        let mut buf = Cursor::new(Vec::new());
+        write_str(&mut buf, "MQTT");
+        buf.write_u8(4).unwrap();
+        buf.write_u8(0x40).unwrap();
+        buf.write_u16::<BigEndian>(0).unwrap();
+
+        assert_eq!(
+            Err(Error::InvalidConnectFlags),
+            Connect::parse(buf.get_ref().as_slice())
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let connect = Connect {
+            protocol_level: 4,
+            clean_session: true,
+            keep_alive: 30,
+            client_id: "client-2",
+            will: Some(Will {
+                qos: QoS::AtLeastOnce,
+                retain: true,
+                topic: "last/will/topic",
+                message: b"bye!",
+            }),
+            username: Some("user"),
+            password: Some(b"pass"),
+        };
+
+        let mut buf = [0u8; 64];
+        let written = connect.encode(&mut buf).unwrap().unwrap();
+        let (parsed, consumed) = Connect::parse(&buf[..written]).unwrap().unwrap();
+
+        assert_eq!(connect, parsed);
+        assert_eq!(written, consumed);
+    }
+
+    #[test]
+    fn encode_small_buffer() {
+        let connect = Connect {
+            protocol_level: 4,
+            clean_session: true,
+            keep_alive: 30,
+            client_id: "client-2",
+            will: None,
+            username: None,
+            password: None,
+        };
+
+        let mut buf = [0u8; 4];
+        assert_eq!(Status::Partial, connect.encode(&mut buf).unwrap());
+    }
+}
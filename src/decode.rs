@@ -0,0 +1,52 @@
+use bytes::BytesMut;
+
+use crate::{packet, Result, Status};
+
+/// Pulls one complete MQTT control packet's worth of bytes off the front
+/// of `buf`, or returns `Ok(None)` if `buf` doesn't yet hold a whole
+/// packet, leaving it untouched so the caller can keep appending data
+/// read off the socket and try again.
+///
+/// This returns the framed bytes themselves rather than a parsed
+/// [`crate::Packet`]: every packet type in this crate borrows from the
+/// slice it was parsed out of, and `buf` keeps being mutated by
+/// subsequent reads, so a `Packet<'_>` borrowing from it couldn't safely
+/// outlive this call. `BytesMut::split_to` hands back the consumed
+/// prefix as its own cheaply-cloned, independently-owned buffer (no
+/// copy, same underlying allocation) that the caller can hold onto and
+/// pass to [`crate::parse`] to get the `Packet`.
+pub fn decode(buf: &mut BytesMut) -> Result<Option<BytesMut>> {
+    match packet::parse(&buf[..])? {
+        Status::Complete((_, consumed)) => Ok(Some(buf.split_to(consumed))),
+        Status::Partial => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse, pingreq::Pingreq, Packet};
+
+    #[test]
+    fn partial_leaves_buffer_untouched() {
+        let mut buf = BytesMut::from(&[0xC0][..]); // PINGREQ header, no length byte yet
+        assert_eq!(None, decode(&mut buf).unwrap());
+        assert_eq!(1, buf.len());
+    }
+
+    #[test]
+    fn decodes_and_advances() {
+        let mut buf = BytesMut::from(&[0xC0, 0x00, 0xC0, 0x00][..]); // two PINGREQs back to back
+
+        let frame = decode(&mut buf).unwrap().unwrap();
+        let (packet, consumed) = parse(&frame).unwrap().unwrap();
+        assert_eq!(Packet::Pingreq(Pingreq), packet);
+        assert_eq!(frame.len(), consumed);
+        assert_eq!(2, buf.len());
+
+        let frame = decode(&mut buf).unwrap().unwrap();
+        let (packet, _) = parse(&frame).unwrap().unwrap();
+        assert_eq!(Packet::Pingreq(Pingreq), packet);
+        assert_eq!(0, buf.len());
+    }
+}
@@ -0,0 +1,47 @@
+use crate::{Error, Result, Status};
+
+/// A parsed DISCONNECT packet (MQTT 3.1.1, section 3.14). Carries no
+/// payload.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Disconnect;
+
+impl Disconnect {
+    pub fn parse(bytes: &[u8]) -> Result<Status<(Disconnect, usize)>> {
+        // In MQTT 3.1.1 the Remaining Length is 0, so a bounded body here
+        // can never hold anything but trailing garbage.
+        if !bytes.is_empty() {
+            return Err(Error::MalformedPacket);
+        }
+
+        Ok(Status::Complete((Disconnect, 0)))
+    }
+
+    /// Encodes the (empty) DISCONNECT body, the mirror image of
+    /// [`Disconnect::parse`].
+    pub fn encode(&self, _buf: &mut [u8]) -> Result<Status<usize>> {
+        Ok(Status::Complete(0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_payload() {
+        assert_eq!(
+            Status::Complete((Disconnect, 0)),
+            Disconnect::parse(&[]).unwrap()
+        );
+    }
+
+    #[test]
+    fn non_empty_body() {
+        assert_eq!(Err(Error::MalformedPacket), Disconnect::parse(&[0]));
+    }
+
+    #[test]
+    fn encode_no_payload() {
+        assert_eq!(Status::Complete(0), Disconnect.encode(&mut []).unwrap());
+    }
+}
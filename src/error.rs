@@ -0,0 +1,56 @@
+use core::str::Utf8Error;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Error {
+    /// The bytes that were supposed to hold a UTF-8 encoded string did not,
+    /// or held a code point disallowed by the MQTT spec (MQTT-1.5.3-2).
+    Utf8,
+
+    /// The QoS value was not one of 0, 1 or 2.
+    InvalidQoS,
+
+    /// A variable byte integer used more than the 4 bytes the spec allows,
+    /// or a value to encode was too large for its length field (over
+    /// 65,535 bytes behind a two-byte length prefix).
+    InvalidLength,
+
+    /// The high nibble of the first header byte did not map to one of the
+    /// 14 control packet types.
+    InvalidPacketType,
+
+    /// A CONNECT packet's Protocol Name was not "MQTT".
+    InvalidProtocolName,
+
+    /// A CONNECT packet's Connect Flags violated one of the MQTT-3.1.2.*
+    /// requirements (reserved bit set, Will QoS/Retain set without the Will
+    /// Flag, or Password Flag set without the User Name Flag).
+    InvalidConnectFlags,
+
+    /// A CONNACK packet's byte 0 had one of its 7 reserved bits set.
+    InvalidConnackFlags,
+
+    /// A CONNACK packet's Connect Return Code was not one of the 6 values
+    /// defined by the spec.
+    InvalidConnectReturnCode,
+
+    /// A SUBACK packet's Return Code was neither a valid granted QoS nor
+    /// the 0x80 failure code.
+    InvalidSubackReturnCode,
+
+    /// An MQTT 5 property block used an identifier the spec doesn't define,
+    /// or its declared length didn't match the properties actually encoded.
+    InvalidProperty,
+
+    /// A packet's body was already bounded to its fixed header's Remaining
+    /// Length, and that body was too short to hold a mandatory field. Unlike
+    /// [`Status::Partial`](crate::Status::Partial), more bytes off the wire
+    /// can never fix this: the body will never grow past what the fixed
+    /// header already promised.
+    MalformedPacket,
+}
+
+impl From<Utf8Error> for Error {
+    fn from(_: Utf8Error) -> Error {
+        Error::Utf8
+    }
+}
@@ -0,0 +1,150 @@
+use crate::{
+    encode_variable_byte_integer, parse_variable_byte_integer, Error, PacketType,
+    PacketTypeFlags, Result, Status,
+};
+
+/// The fixed header that prefixes every MQTT control packet: a packet type
+/// plus four flag bits packed into the first byte, followed by the
+/// Remaining Length of the variable header and payload.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Header {
+    pub packet_type: PacketType,
+    pub flags: PacketTypeFlags,
+    pub remaining_length: u32,
+}
+
+impl Header {
+    /// Parses the fixed header, returning the `Header` together with the
+    /// number of bytes it occupied (1 plus however many the Remaining
+    /// Length field took).
+    pub fn parse(bytes: &[u8]) -> Result<Status<(Header, usize)>> {
+        if bytes.is_empty() {
+            return Ok(Status::Partial);
+        }
+
+        let byte0 = bytes[0];
+        let packet_type = match byte0 >> 4 {
+            1 => PacketType::Connect,
+            2 => PacketType::Connack,
+            3 => PacketType::Publish,
+            4 => PacketType::Puback,
+            5 => PacketType::Pubrec,
+            6 => PacketType::Pubrel,
+            7 => PacketType::Pubcomp,
+            8 => PacketType::Subscribe,
+            9 => PacketType::Suback,
+            10 => PacketType::Unsubscribe,
+            11 => PacketType::Unsuback,
+            12 => PacketType::Pingreq,
+            13 => PacketType::Pingresp,
+            14 => PacketType::Disconnect,
+            _ => return Err(Error::InvalidPacketType),
+        };
+        let flags = byte0 & 0x0F;
+
+        let (remaining_length, len_size) = complete!(parse_variable_byte_integer(&bytes[1..])?);
+
+        Ok(Status::Complete((
+            Header {
+                packet_type,
+                flags,
+                remaining_length,
+            },
+            1 + len_size,
+        )))
+    }
+
+    /// Encodes the fixed header, the mirror image of [`Header::parse`].
+    pub fn encode(&self, buf: &mut [u8]) -> Result<Status<usize>> {
+        if buf.is_empty() {
+            return Ok(Status::Partial);
+        }
+
+        buf[0] = (self.packet_type.to_u8() << 4) | (self.flags & 0x0F);
+
+        let len_size = complete!(encode_variable_byte_integer(
+            self.remaining_length,
+            &mut buf[1..]
+        )?);
+
+        Ok(Status::Complete(1 + len_size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_buffer() {
+        assert_eq!(Status::Partial, Header::parse(&[]).unwrap());
+        assert_eq!(Status::Partial, Header::parse(&[0x10]).unwrap());
+        assert_eq!(Status::Partial, Header::parse(&[0x10, 0x80]).unwrap());
+    }
+
+    #[test]
+    fn connect_header() {
+        let (header, consumed) = Header::parse(&[0x10, 0x0A]).unwrap().unwrap();
+        assert_eq!(PacketType::Connect, header.packet_type);
+        assert_eq!(0, header.flags);
+        assert_eq!(10, header.remaining_length);
+        assert_eq!(2, consumed);
+    }
+
+    #[test]
+    fn publish_flags() {
+        let (header, consumed) = Header::parse(&[0x3D, 0x00]).unwrap().unwrap();
+        assert_eq!(PacketType::Publish, header.packet_type);
+        assert_eq!(0x0D, header.flags);
+        assert_eq!(0, header.remaining_length);
+        assert_eq!(2, consumed);
+    }
+
+    #[test]
+    fn multi_byte_remaining_length() {
+        let (header, consumed) = Header::parse(&[0xE0, 0xD0, 0x06]).unwrap().unwrap();
+        assert_eq!(PacketType::Disconnect, header.packet_type);
+        assert_eq!(0x0D0 & 0x7F | (0x06 << 7), header.remaining_length);
+        assert_eq!(3, consumed);
+    }
+
+    #[test]
+    fn invalid_packet_type() {
+        assert_eq!(Err(Error::InvalidPacketType), Header::parse(&[0x00]));
+    }
+
+    #[test]
+    fn length_too_long() {
+        assert_eq!(
+            Err(Error::InvalidLength),
+            Header::parse(&[0x10, 0xFF, 0xFF, 0xFF, 0xFF, 0x7F])
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let header = Header {
+            packet_type: PacketType::Publish,
+            flags: 0x0D,
+            remaining_length: 321,
+        };
+
+        let mut buf = [0u8; 5];
+        let written = header.encode(&mut buf).unwrap().unwrap();
+        let (parsed, consumed) = Header::parse(&buf[..written]).unwrap().unwrap();
+
+        assert_eq!(header, parsed);
+        assert_eq!(written, consumed);
+    }
+
+    #[test]
+    fn encode_small_buffer() {
+        let header = Header {
+            packet_type: PacketType::Connect,
+            flags: 0,
+            remaining_length: 0,
+        };
+
+        assert_eq!(Status::Partial, header.encode(&mut []).unwrap());
+    }
+}
@@ -5,8 +5,13 @@ extern crate byteorder;
 #[cfg(feature = "std")]
 extern crate std as core;
 
+#[cfg(feature = "bytes")]
+extern crate bytes;
+
 #[cfg(test)]
 extern crate rayon;
+#[cfg(test)]
+extern crate proptest;
 
 use byteorder::{BigEndian, ByteOrder};
 use core::result;
@@ -28,6 +33,34 @@ pub use header::Header;
 pub mod connect;
 pub use connect::Connect;
 
+pub mod connack;
+pub mod disconnect;
+pub mod pingreq;
+pub mod pingresp;
+pub mod puback;
+pub mod pubcomp;
+pub mod publish;
+pub mod pubrec;
+pub mod pubrel;
+pub mod suback;
+pub mod subscribe;
+pub mod unsuback;
+pub mod unsubscribe;
+
+pub mod packet;
+pub use packet::{encode, parse, Packet};
+
+pub mod properties;
+pub use properties::{parse_properties, Properties};
+
+#[cfg(feature = "bytes")]
+pub mod decode;
+#[cfg(feature = "bytes")]
+pub use decode::decode;
+
+#[cfg(test)]
+mod proptest_strategies;
+
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum QoS {
     AtMostOnce,
@@ -44,6 +77,14 @@ impl QoS {
             _ => Err(Error::InvalidQoS),
         }
     }
+
+    pub fn to_u8(self) -> u8 {
+        match self {
+            QoS::AtMostOnce => 0,
+            QoS::AtLeastOnce => 1,
+            QoS::ExactlyOnce => 2,
+        }
+    }
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
@@ -64,9 +105,30 @@ pub enum PacketType {
     Disconnect,
 }
 
+impl PacketType {
+    pub fn to_u8(self) -> u8 {
+        match self {
+            PacketType::Connect => 1,
+            PacketType::Connack => 2,
+            PacketType::Publish => 3,
+            PacketType::Puback => 4,
+            PacketType::Pubrec => 5,
+            PacketType::Pubrel => 6,
+            PacketType::Pubcomp => 7,
+            PacketType::Subscribe => 8,
+            PacketType::Suback => 9,
+            PacketType::Unsubscribe => 10,
+            PacketType::Unsuback => 11,
+            PacketType::Pingreq => 12,
+            PacketType::Pingresp => 13,
+            PacketType::Disconnect => 14,
+        }
+    }
+}
+
 pub type Result<T> = result::Result<T, Error>;
 
-pub fn parse_string(bytes: &[u8]) -> Result<Status<&str>> {
+pub fn parse_string(bytes: &[u8]) -> Result<Status<(&str, usize)>> {
     // we need at least the 2 bytes to figure out length of the utf-8 encoded
     // string in bytes
     if bytes.len() < 2 {
@@ -91,11 +153,11 @@ pub fn parse_string(bytes: &[u8]) -> Result<Status<&str>> {
     if val.chars().any(|ch| ch == '\u{0000}') {
         Err(Error::Utf8)
     } else {
-        Ok(Status::Complete(val))
+        Ok(Status::Complete((val, 2 + len as usize)))
     }
 }
 
-pub fn parse_len_prefixed_bytes(bytes: &[u8]) -> Result<Status<&[u8]>> {
+pub fn parse_len_prefixed_bytes(bytes: &[u8]) -> Result<Status<(&[u8], usize)>> {
     // we need at least the 2 bytes to figure out length of the payload
     if bytes.len() < 2 {
         return Ok(Status::Partial);
@@ -106,7 +168,89 @@ pub fn parse_len_prefixed_bytes(bytes: &[u8]) -> Result<Status<&[u8]>> {
         return Ok(Status::Partial);
     }
 
-    Ok(Status::Complete(&bytes[2..(len + 2) as usize]))
+    Ok(Status::Complete((&bytes[2..(len + 2) as usize], 2 + len as usize)))
+}
+
+/// Encodes `s` as a two-byte big-endian length prefix followed by its UTF-8
+/// bytes, the mirror image of [`parse_string`].
+pub fn encode_string(s: &str, buf: &mut [u8]) -> Result<Status<usize>> {
+    encode_len_prefixed_bytes(s.as_bytes(), buf)
+}
+
+/// Encodes `bytes` as a two-byte big-endian length prefix followed by the
+/// bytes themselves, the mirror image of [`parse_len_prefixed_bytes`].
+pub fn encode_len_prefixed_bytes(bytes: &[u8], buf: &mut [u8]) -> Result<Status<usize>> {
+    if bytes.len() > u16::MAX as usize {
+        return Err(Error::InvalidLength);
+    }
+
+    if buf.len() < 2 + bytes.len() {
+        return Ok(Status::Partial);
+    }
+
+    BigEndian::write_u16(buf, bytes.len() as u16);
+    buf[2..2 + bytes.len()].copy_from_slice(bytes);
+
+    Ok(Status::Complete(2 + bytes.len()))
+}
+
+/// Decodes an MQTT variable byte integer: a little-endian base-128 encoding,
+/// capped at 4 bytes, where the top bit of each byte signals that another
+/// byte follows. Used for the fixed header's Remaining Length and, in
+/// MQTT 5, several property values.
+pub fn parse_variable_byte_integer(bytes: &[u8]) -> Result<Status<(u32, usize)>> {
+    let mut value: u32 = 0;
+    let mut multiplier: u32 = 1;
+
+    for (idx, &byte) in bytes.iter().enumerate() {
+        if multiplier > 128 * 128 * 128 {
+            return Err(Error::InvalidLength);
+        }
+
+        value += (byte & 0x7F) as u32 * multiplier;
+
+        if byte & 0x80 == 0 {
+            return Ok(Status::Complete((value, idx + 1)));
+        }
+
+        multiplier *= 128;
+    }
+
+    Ok(Status::Partial)
+}
+
+/// The largest value `parse_variable_byte_integer`/`encode_variable_byte_integer`
+/// can represent in the spec's 4-byte encoding.
+const MAX_VARIABLE_BYTE_INTEGER: u32 = 128 * 128 * 128 * 128 - 1;
+
+/// Encodes `value` using MQTT's variable byte integer scheme, the mirror
+/// image of [`parse_variable_byte_integer`].
+pub fn encode_variable_byte_integer(value: u32, buf: &mut [u8]) -> Result<Status<usize>> {
+    if value > MAX_VARIABLE_BYTE_INTEGER {
+        return Err(Error::InvalidLength);
+    }
+
+    let mut remaining = value;
+    let mut idx = 0;
+
+    loop {
+        if idx >= buf.len() {
+            return Ok(Status::Partial);
+        }
+
+        let mut byte = (remaining % 128) as u8;
+        remaining /= 128;
+        if remaining > 0 {
+            byte |= 0x80;
+        }
+
+        buf[idx] = byte;
+        idx += 1;
+
+        if remaining == 0 {
+            return Ok(Status::Complete(idx));
+        }
+    }
 }
 
 #[cfg(test)]
@@ -132,7 +276,7 @@ mod tests {
         #[test]
         fn empty_str() {
             let buf = [0u8; 2];
-            assert_eq!(Status::Complete(""), parse_string(&buf).unwrap());
+            assert_eq!(Status::Complete(("", 2)), parse_string(&buf).unwrap());
         }
 
         #[test]
@@ -142,7 +286,7 @@ mod tests {
             buf.write_u16::<BigEndian>(inp.len() as u16).unwrap();
             buf.write(inp.as_bytes()).unwrap();
             assert_eq!(
-                Status::Complete(inp),
+                Status::Complete((inp, 2 + inp.len())),
                 parse_string(buf.get_ref().as_ref()).unwrap()
             );
         }
@@ -186,7 +330,7 @@ mod tests {
         fn empty_bytes() {
             let buf = [0u8; 2];
             assert_eq!(
-                Status::Complete(&buf[0..0]),
+                Status::Complete((&buf[0..0], 2)),
                 parse_len_prefixed_bytes(&buf).unwrap()
             );
         }
@@ -198,9 +342,146 @@ mod tests {
             buf.write_u16::<BigEndian>(inp.len() as u16).unwrap();
             buf.write(inp).unwrap();
             assert_eq!(
-                Status::Complete(inp),
+                Status::Complete((inp, 2 + inp.len())),
                 parse_len_prefixed_bytes(buf.get_ref().as_ref()).unwrap()
             );
         }
     }
+
+    mod parse_variable_byte_integer {
+        use super::*;
+
+        #[test]
+        fn small_buffer() {
+            assert_eq!(Status::Partial, parse_variable_byte_integer(&[]).unwrap());
+            assert_eq!(
+                Status::Partial,
+                parse_variable_byte_integer(&[0x80]).unwrap()
+            );
+        }
+
+        #[test]
+        fn single_byte() {
+            assert_eq!(
+                Status::Complete((0, 1)),
+                parse_variable_byte_integer(&[0x00]).unwrap()
+            );
+            assert_eq!(
+                Status::Complete((127, 1)),
+                parse_variable_byte_integer(&[0x7F]).unwrap()
+            );
+        }
+
+        #[test]
+        fn multi_byte() {
+            // 128 encodes as 0x80 0x01
+            assert_eq!(
+                Status::Complete((128, 2)),
+                parse_variable_byte_integer(&[0x80, 0x01]).unwrap()
+            );
+            // 16384 encodes as 0x80 0x80 0x01
+            assert_eq!(
+                Status::Complete((16384, 3)),
+                parse_variable_byte_integer(&[0x80, 0x80, 0x01]).unwrap()
+            );
+        }
+
+        #[test]
+        fn max_value() {
+            // 268,435,455 is the largest value the 4-byte encoding allows.
+            assert_eq!(
+                Status::Complete((268_435_455, 4)),
+                parse_variable_byte_integer(&[0xFF, 0xFF, 0xFF, 0x7F]).unwrap()
+            );
+        }
+
+        #[test]
+        fn too_long() {
+            assert_eq!(
+                Err(Error::InvalidLength),
+                parse_variable_byte_integer(&[0xFF, 0xFF, 0xFF, 0xFF, 0x7F])
+            );
+        }
+    }
+
+    mod encode_string {
+        use super::*;
+
+        #[test]
+        fn small_buffer() {
+            let mut buf = [0u8; 1];
+            assert_eq!(Status::Partial, encode_string("hi", &mut buf).unwrap());
+        }
+
+        #[test]
+        fn round_trip() {
+            let mut buf = [0u8; 32];
+            let written = encode_string("don't panic!", &mut buf).unwrap().unwrap();
+            assert_eq!(
+                Status::Complete(("don't panic!", written)),
+                parse_string(&buf[..written]).unwrap()
+            );
+        }
+    }
+
+    mod encode_len_prefixed_bytes {
+        use super::*;
+
+        #[test]
+        fn small_buffer() {
+            let mut buf = [0u8; 1];
+            assert_eq!(
+                Status::Partial,
+                encode_len_prefixed_bytes(b"hi", &mut buf).unwrap()
+            );
+        }
+
+        #[test]
+        fn round_trip() {
+            let mut buf = [0u8; 32];
+            let written = encode_len_prefixed_bytes(b"don't panic!", &mut buf)
+                .unwrap()
+                .unwrap();
+            assert_eq!(
+                Status::Complete((b"don't panic!".as_ref(), written)),
+                parse_len_prefixed_bytes(&buf[..written]).unwrap()
+            );
+        }
+    }
+
+    mod encode_variable_byte_integer {
+        use super::*;
+
+        #[test]
+        fn small_buffer() {
+            let mut buf = [0u8; 0];
+            assert_eq!(
+                Status::Partial,
+                encode_variable_byte_integer(128, &mut buf).unwrap()
+            );
+        }
+
+        #[test]
+        fn round_trip() {
+            for &value in &[0, 127, 128, 16_384, 268_435_455] {
+                let mut buf = [0u8; 4];
+                let written = encode_variable_byte_integer(value, &mut buf)
+                    .unwrap()
+                    .unwrap();
+                assert_eq!(
+                    Status::Complete((value, written)),
+                    parse_variable_byte_integer(&buf[..written]).unwrap()
+                );
+            }
+        }
+
+        #[test]
+        fn too_large() {
+            let mut buf = [0u8; 4];
+            assert_eq!(
+                Err(Error::InvalidLength),
+                encode_variable_byte_integer(268_435_456, &mut buf)
+            );
+        }
+    }
 }
@@ -0,0 +1,245 @@
+use crate::connack::Connack;
+use crate::connect::Connect;
+use crate::disconnect::Disconnect;
+use crate::pingreq::Pingreq;
+use crate::pingresp::Pingresp;
+use crate::puback::Puback;
+use crate::pubcomp::Pubcomp;
+use crate::publish::Publish;
+use crate::pubrec::Pubrec;
+use crate::pubrel::Pubrel;
+use crate::suback::Suback;
+use crate::subscribe::Subscribe;
+use crate::unsuback::Unsuback;
+use crate::unsubscribe::Unsubscribe;
+use crate::{Header, PacketType, Result, Status};
+
+/// A fully parsed MQTT control packet, tagged by its type. Borrows from the
+/// input buffer wherever the wire format allows it.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Packet<'a> {
+    Connect(Connect<'a>),
+    Connack(Connack<'a>),
+    Publish(Publish<'a>),
+    Puback(Puback),
+    Pubrec(Pubrec),
+    Pubrel(Pubrel),
+    Pubcomp(Pubcomp),
+    Subscribe(Subscribe<'a>),
+    Suback(Suback<'a>),
+    Unsubscribe(Unsubscribe<'a>),
+    Unsuback(Unsuback),
+    Pingreq(Pingreq),
+    Pingresp(Pingresp),
+    Disconnect(Disconnect),
+}
+
+/// Reads one MQTT control packet off the front of `bytes`: the fixed
+/// header, then the variable header and payload belonging to whichever
+/// packet type the header names. Returns `Status::Partial` when `bytes`
+/// doesn't yet hold the whole packet, otherwise the `Packet` together with
+/// the total number of bytes it occupied.
+pub fn parse<'a>(bytes: &'a [u8]) -> Result<Status<(Packet<'a>, usize)>> {
+    let (header, header_len) = complete!(Header::parse(bytes)?);
+
+    let body_end = header_len + header.remaining_length as usize;
+    if bytes.len() < body_end {
+        return Ok(Status::Partial);
+    }
+    let body = &bytes[header_len..body_end];
+
+    let packet = match header.packet_type {
+        PacketType::Connect => Packet::Connect(complete!(Connect::parse(body)?).0),
+        PacketType::Connack => Packet::Connack(complete!(Connack::parse(body)?).0),
+        PacketType::Publish => Packet::Publish(complete!(Publish::parse(header.flags, body)?).0),
+        PacketType::Puback => Packet::Puback(complete!(Puback::parse(body)?).0),
+        PacketType::Pubrec => Packet::Pubrec(complete!(Pubrec::parse(body)?).0),
+        PacketType::Pubrel => Packet::Pubrel(complete!(Pubrel::parse(body)?).0),
+        PacketType::Pubcomp => Packet::Pubcomp(complete!(Pubcomp::parse(body)?).0),
+        PacketType::Subscribe => Packet::Subscribe(complete!(Subscribe::parse(body)?).0),
+        PacketType::Suback => Packet::Suback(complete!(Suback::parse(body)?).0),
+        PacketType::Unsubscribe => {
+            Packet::Unsubscribe(complete!(Unsubscribe::parse(body)?).0)
+        }
+        PacketType::Unsuback => Packet::Unsuback(complete!(Unsuback::parse(body)?).0),
+        PacketType::Pingreq => Packet::Pingreq(complete!(Pingreq::parse(body)?).0),
+        PacketType::Pingresp => Packet::Pingresp(complete!(Pingresp::parse(body)?).0),
+        PacketType::Disconnect => Packet::Disconnect(complete!(Disconnect::parse(body)?).0),
+    };
+
+    Ok(Status::Complete((packet, body_end)))
+}
+
+/// The largest a fixed header can be: 1 type/flags byte plus the 4 bytes
+/// the Remaining Length encoding can take at most.
+const MAX_HEADER_LEN: usize = 5;
+
+/// Encodes one MQTT control packet into `buf`, the mirror image of
+/// [`parse`]. The body is encoded at offset `MAX_HEADER_LEN` and then moved
+/// down behind the finished header, so `buf` needs `MAX_HEADER_LEN` bytes
+/// plus the body's length even when the header turns out shorter. Returns
+/// `Status::Partial` if `buf` is shorter than that, otherwise the total
+/// number of bytes written.
+pub fn encode(packet: &Packet, buf: &mut [u8]) -> Result<Status<usize>> {
+    if buf.len() < MAX_HEADER_LEN {
+        return Ok(Status::Partial);
+    }
+
+    let (packet_type, flags, body_len) = match packet {
+        Packet::Connect(p) => (
+            PacketType::Connect,
+            0,
+            complete!(p.encode(&mut buf[MAX_HEADER_LEN..])?),
+        ),
+        Packet::Connack(p) => (
+            PacketType::Connack,
+            0,
+            complete!(p.encode(&mut buf[MAX_HEADER_LEN..])?),
+        ),
+        Packet::Publish(p) => (
+            PacketType::Publish,
+            p.flags(),
+            complete!(p.encode(&mut buf[MAX_HEADER_LEN..])?),
+        ),
+        Packet::Puback(p) => (
+            PacketType::Puback,
+            0,
+            complete!(p.encode(&mut buf[MAX_HEADER_LEN..])?),
+        ),
+        Packet::Pubrec(p) => (
+            PacketType::Pubrec,
+            0,
+            complete!(p.encode(&mut buf[MAX_HEADER_LEN..])?),
+        ),
+        Packet::Pubrel(p) => (
+            PacketType::Pubrel,
+            0x02,
+            complete!(p.encode(&mut buf[MAX_HEADER_LEN..])?),
+        ),
+        Packet::Pubcomp(p) => (
+            PacketType::Pubcomp,
+            0,
+            complete!(p.encode(&mut buf[MAX_HEADER_LEN..])?),
+        ),
+        Packet::Subscribe(p) => (
+            PacketType::Subscribe,
+            0x02,
+            complete!(p.encode(&mut buf[MAX_HEADER_LEN..])?),
+        ),
+        Packet::Suback(p) => (
+            PacketType::Suback,
+            0,
+            complete!(p.encode(&mut buf[MAX_HEADER_LEN..])?),
+        ),
+        Packet::Unsubscribe(p) => (
+            PacketType::Unsubscribe,
+            0x02,
+            complete!(p.encode(&mut buf[MAX_HEADER_LEN..])?),
+        ),
+        Packet::Unsuback(p) => (
+            PacketType::Unsuback,
+            0,
+            complete!(p.encode(&mut buf[MAX_HEADER_LEN..])?),
+        ),
+        Packet::Pingreq(p) => (
+            PacketType::Pingreq,
+            0,
+            complete!(p.encode(&mut buf[MAX_HEADER_LEN..])?),
+        ),
+        Packet::Pingresp(p) => (
+            PacketType::Pingresp,
+            0,
+            complete!(p.encode(&mut buf[MAX_HEADER_LEN..])?),
+        ),
+        Packet::Disconnect(p) => (
+            PacketType::Disconnect,
+            0,
+            complete!(p.encode(&mut buf[MAX_HEADER_LEN..])?),
+        ),
+    };
+
+    let header = Header {
+        packet_type,
+        flags,
+        remaining_length: body_len as u32,
+    };
+
+    let mut header_buf = [0u8; MAX_HEADER_LEN];
+    let header_len = complete!(header.encode(&mut header_buf)?);
+
+    buf.copy_within(MAX_HEADER_LEN..MAX_HEADER_LEN + body_len, header_len);
+    buf[..header_len].copy_from_slice(&header_buf[..header_len]);
+
+    Ok(Status::Complete(header_len + body_len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::QoS;
+
+    #[test]
+    fn small_buffer() {
+        assert_eq!(Status::Partial, parse(&[]).unwrap());
+        assert_eq!(Status::Partial, parse(&[0xE0]).unwrap());
+    }
+
+    #[test]
+    fn incomplete_body() {
+        // DISCONNECT header claims a 2 byte body that isn't there yet.
+        assert_eq!(Status::Partial, parse(&[0xE0, 0x02]).unwrap());
+    }
+
+    #[test]
+    fn pingreq() {
+        let (packet, consumed) = parse(&[0xC0, 0x00]).unwrap().unwrap();
+        assert_eq!(Packet::Pingreq(Pingreq), packet);
+        assert_eq!(2, consumed);
+    }
+
+    #[test]
+    fn puback() {
+        let bytes = [0x40, 0x02, 0x00, 0x2A];
+        let (packet, consumed) = parse(&bytes).unwrap().unwrap();
+        assert_eq!(Packet::Puback(Puback { packet_id: 42 }), packet);
+        assert_eq!(bytes.len(), consumed);
+    }
+
+    #[test]
+    fn round_trip_puback() {
+        let packet = Packet::Puback(Puback { packet_id: 42 });
+
+        let mut buf = [0u8; 16];
+        let written = encode(&packet, &mut buf).unwrap().unwrap();
+        let (parsed, consumed) = parse(&buf[..written]).unwrap().unwrap();
+
+        assert_eq!(packet, parsed);
+        assert_eq!(written, consumed);
+    }
+
+    #[test]
+    fn round_trip_publish() {
+        let packet = Packet::Publish(Publish {
+            dup: false,
+            qos: QoS::AtLeastOnce,
+            retain: true,
+            topic_name: "a/b",
+            packet_id: Some(9),
+            payload: b"hello",
+        });
+
+        let mut buf = [0u8; 32];
+        let written = encode(&packet, &mut buf).unwrap().unwrap();
+        let (parsed, consumed) = parse(&buf[..written]).unwrap().unwrap();
+
+        assert_eq!(packet, parsed);
+        assert_eq!(written, consumed);
+    }
+
+    #[test]
+    fn encode_small_buffer() {
+        let packet = Packet::Pingreq(Pingreq);
+        let mut buf = [0u8; 4];
+        assert_eq!(Status::Partial, encode(&packet, &mut buf).unwrap());
+    }
+}
@@ -0,0 +1,43 @@
+use crate::{Error, Result, Status};
+
+/// A parsed PINGREQ packet (MQTT 3.1.1, section 3.12). Carries no payload.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Pingreq;
+
+impl Pingreq {
+    pub fn parse(bytes: &[u8]) -> Result<Status<(Pingreq, usize)>> {
+        // MQTT-3.12.1-1: the Remaining Length is 0, so a bounded body here
+        // can never hold anything but trailing garbage.
+        if !bytes.is_empty() {
+            return Err(Error::MalformedPacket);
+        }
+
+        Ok(Status::Complete((Pingreq, 0)))
+    }
+
+    /// Encodes the (empty) PINGREQ body, the mirror image of
+    /// [`Pingreq::parse`].
+    pub fn encode(&self, _buf: &mut [u8]) -> Result<Status<usize>> {
+        Ok(Status::Complete(0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_payload() {
+        assert_eq!(Status::Complete((Pingreq, 0)), Pingreq::parse(&[]).unwrap());
+    }
+
+    #[test]
+    fn non_empty_body() {
+        assert_eq!(Err(Error::MalformedPacket), Pingreq::parse(&[0]));
+    }
+
+    #[test]
+    fn encode_no_payload() {
+        assert_eq!(Status::Complete(0), Pingreq.encode(&mut []).unwrap());
+    }
+}
@@ -0,0 +1,46 @@
+use crate::{Error, Result, Status};
+
+/// A parsed PINGRESP packet (MQTT 3.1.1, section 3.13). Carries no payload.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Pingresp;
+
+impl Pingresp {
+    pub fn parse(bytes: &[u8]) -> Result<Status<(Pingresp, usize)>> {
+        // MQTT-3.13.1-1: the Remaining Length is 0, so a bounded body here
+        // can never hold anything but trailing garbage.
+        if !bytes.is_empty() {
+            return Err(Error::MalformedPacket);
+        }
+
+        Ok(Status::Complete((Pingresp, 0)))
+    }
+
+    /// Encodes the (empty) PINGRESP body, the mirror image of
+    /// [`Pingresp::parse`].
+    pub fn encode(&self, _buf: &mut [u8]) -> Result<Status<usize>> {
+        Ok(Status::Complete(0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_payload() {
+        assert_eq!(
+            Status::Complete((Pingresp, 0)),
+            Pingresp::parse(&[]).unwrap()
+        );
+    }
+
+    #[test]
+    fn non_empty_body() {
+        assert_eq!(Err(Error::MalformedPacket), Pingresp::parse(&[0]));
+    }
+
+    #[test]
+    fn encode_no_payload() {
+        assert_eq!(Status::Complete(0), Pingresp.encode(&mut []).unwrap());
+    }
+}
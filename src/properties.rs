@@ -0,0 +1,272 @@
+use byteorder::{BigEndian, ByteOrder};
+
+use crate::{
+    parse_len_prefixed_bytes, parse_string, parse_variable_byte_integer, Error, Result, Status,
+};
+
+/// One entry of an MQTT 5 property block (MQTT 5.0, section 2.2.2).
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Property<'a> {
+    PayloadFormatIndicator(u8),
+    MessageExpiryInterval(u32),
+    ContentType(&'a str),
+    ResponseTopic(&'a str),
+    CorrelationData(&'a [u8]),
+    SubscriptionIdentifier(u32),
+    SessionExpiryInterval(u32),
+    AssignedClientIdentifier(&'a str),
+    ServerKeepAlive(u16),
+    AuthenticationMethod(&'a str),
+    AuthenticationData(&'a [u8]),
+    RequestProblemInformation(u8),
+    WillDelayInterval(u32),
+    RequestResponseInformation(u8),
+    ResponseInformation(&'a str),
+    ServerReference(&'a str),
+    ReasonString(&'a str),
+    ReceiveMaximum(u16),
+    TopicAliasMaximum(u16),
+    TopicAlias(u16),
+    MaximumQoS(u8),
+    RetainAvailable(u8),
+    UserProperty(&'a str, &'a str),
+    MaximumPacketSize(u32),
+    WildcardSubscriptionAvailable(u8),
+    SubscriptionIdentifierAvailable(u8),
+    SharedSubscriptionAvailable(u8),
+}
+
+/// Reads the one-byte value of a property whose type is "Byte".
+fn read_byte(bytes: &[u8]) -> Result<(u8, usize)> {
+    if bytes.is_empty() {
+        return Err(Error::InvalidProperty);
+    }
+    Ok((bytes[0], 1))
+}
+
+/// Reads the two-byte value of a property whose type is "Two Byte Integer".
+fn read_u16(bytes: &[u8]) -> Result<(u16, usize)> {
+    if bytes.len() < 2 {
+        return Err(Error::InvalidProperty);
+    }
+    Ok((BigEndian::read_u16(bytes), 2))
+}
+
+/// Reads the four-byte value of a property whose type is "Four Byte
+/// Integer".
+fn read_u32(bytes: &[u8]) -> Result<(u32, usize)> {
+    if bytes.len() < 4 {
+        return Err(Error::InvalidProperty);
+    }
+    Ok((BigEndian::read_u32(bytes), 4))
+}
+
+/// Reads the value of a property whose type is "Variable Byte Integer".
+fn read_varint(bytes: &[u8]) -> Result<(u32, usize)> {
+    match parse_variable_byte_integer(bytes)? {
+        Status::Complete(pair) => Ok(pair),
+        Status::Partial => Err(Error::InvalidProperty),
+    }
+}
+
+/// Reads the value of a property whose type is "UTF-8 Encoded String".
+fn read_str(bytes: &[u8]) -> Result<(&str, usize)> {
+    match parse_string(bytes) {
+        Ok(Status::Complete(pair)) => Ok(pair),
+        Ok(Status::Partial) => Err(Error::InvalidProperty),
+        Err(err) => Err(err),
+    }
+}
+
+/// Reads the value of a property whose type is "Binary Data".
+fn read_bytes(bytes: &[u8]) -> Result<(&[u8], usize)> {
+    match parse_len_prefixed_bytes(bytes) {
+        Ok(Status::Complete(pair)) => Ok(pair),
+        Ok(Status::Partial) => Err(Error::InvalidProperty),
+        Err(err) => Err(err),
+    }
+}
+
+/// A parsed MQTT 5 property block. The list of properties is kept as a
+/// borrowed, zero-copy iterator rather than a `Vec`, matching
+/// [`crate::subscribe::Subscribe::topic_filters`] and friends; `Properties`
+/// itself is the iterator, since a property block has no other fields to
+/// carry alongside it.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Properties<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Properties<'a> {
+    /// An empty property block: yields no properties. Useful for packet
+    /// types that carry a `Properties` field only under MQTT 5, so that
+    /// parsing the same packet under MQTT 3.1.1 (which has no property
+    /// block at all) still has a `Properties` value to hand back.
+    pub fn empty() -> Properties<'a> {
+        Properties { bytes: &[] }
+    }
+}
+
+impl<'a> Iterator for Properties<'a> {
+    type Item = Result<Property<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bytes.is_empty() {
+            return None;
+        }
+
+        let id = self.bytes[0];
+        let rest = &self.bytes[1..];
+
+        let result = match id {
+            0x01 => read_byte(rest).map(|(v, len)| (Property::PayloadFormatIndicator(v), 1 + len)),
+            0x02 => read_u32(rest).map(|(v, len)| (Property::MessageExpiryInterval(v), 1 + len)),
+            0x03 => read_str(rest).map(|(v, len)| (Property::ContentType(v), 1 + len)),
+            0x08 => read_str(rest).map(|(v, len)| (Property::ResponseTopic(v), 1 + len)),
+            0x09 => read_bytes(rest).map(|(v, len)| (Property::CorrelationData(v), 1 + len)),
+            0x0B => read_varint(rest).map(|(v, len)| (Property::SubscriptionIdentifier(v), 1 + len)),
+            0x11 => read_u32(rest).map(|(v, len)| (Property::SessionExpiryInterval(v), 1 + len)),
+            0x12 => {
+                read_str(rest).map(|(v, len)| (Property::AssignedClientIdentifier(v), 1 + len))
+            }
+            0x13 => read_u16(rest).map(|(v, len)| (Property::ServerKeepAlive(v), 1 + len)),
+            0x15 => read_str(rest).map(|(v, len)| (Property::AuthenticationMethod(v), 1 + len)),
+            0x16 => read_bytes(rest).map(|(v, len)| (Property::AuthenticationData(v), 1 + len)),
+            0x17 => {
+                read_byte(rest).map(|(v, len)| (Property::RequestProblemInformation(v), 1 + len))
+            }
+            0x18 => read_u32(rest).map(|(v, len)| (Property::WillDelayInterval(v), 1 + len)),
+            0x19 => {
+                read_byte(rest).map(|(v, len)| (Property::RequestResponseInformation(v), 1 + len))
+            }
+            0x1A => read_str(rest).map(|(v, len)| (Property::ResponseInformation(v), 1 + len)),
+            0x1C => read_str(rest).map(|(v, len)| (Property::ServerReference(v), 1 + len)),
+            0x1F => read_str(rest).map(|(v, len)| (Property::ReasonString(v), 1 + len)),
+            0x21 => read_u16(rest).map(|(v, len)| (Property::ReceiveMaximum(v), 1 + len)),
+            0x22 => read_u16(rest).map(|(v, len)| (Property::TopicAliasMaximum(v), 1 + len)),
+            0x23 => read_u16(rest).map(|(v, len)| (Property::TopicAlias(v), 1 + len)),
+            0x24 => read_byte(rest).map(|(v, len)| (Property::MaximumQoS(v), 1 + len)),
+            0x25 => read_byte(rest).map(|(v, len)| (Property::RetainAvailable(v), 1 + len)),
+            0x26 => (|| {
+                let (key, key_len) = read_str(rest)?;
+                let (val, val_len) = read_str(&rest[key_len..])?;
+                Ok((Property::UserProperty(key, val), key_len + val_len))
+            })()
+            .map(|(prop, len)| (prop, 1 + len)),
+            0x27 => read_u32(rest).map(|(v, len)| (Property::MaximumPacketSize(v), 1 + len)),
+            0x28 => read_byte(rest)
+                .map(|(v, len)| (Property::WildcardSubscriptionAvailable(v), 1 + len)),
+            0x29 => read_byte(rest)
+                .map(|(v, len)| (Property::SubscriptionIdentifierAvailable(v), 1 + len)),
+            0x2A => {
+                read_byte(rest).map(|(v, len)| (Property::SharedSubscriptionAvailable(v), 1 + len))
+            }
+            _ => Err(Error::InvalidProperty),
+        };
+
+        match result {
+            Ok((property, consumed)) => {
+                self.bytes = &self.bytes[consumed..];
+                Some(Ok(property))
+            }
+            Err(err) => {
+                self.bytes = &[];
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+/// Parses an MQTT 5 property block: a Property Length prefix encoded as a
+/// variable byte integer, followed by that many bytes of identifier/value
+/// pairs. Returns the `Properties` iterator together with the total number
+/// of bytes consumed, including the length prefix itself.
+///
+/// Iterating the result only ever reads within the declared Property
+/// Length, so a property whose value runs past the end of that block
+/// surfaces as `Error::InvalidProperty` rather than silently reading into
+/// whatever follows.
+pub fn parse_properties(bytes: &[u8]) -> Result<Status<(Properties<'_>, usize)>> {
+    let (len, len_size) = complete!(parse_variable_byte_integer(bytes)?);
+    let len = len as usize;
+
+    if bytes.len() < len_size + len {
+        return Ok(Status::Partial);
+    }
+
+    let properties = Properties {
+        bytes: &bytes[len_size..len_size + len],
+    };
+
+    Ok(Status::Complete((properties, len_size + len)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_buffer() {
+        assert_eq!(Status::Partial, parse_properties(&[]).unwrap());
+        // Length prefix claims 5 bytes of properties but none follow.
+        assert_eq!(Status::Partial, parse_properties(&[0x05]).unwrap());
+    }
+
+    #[test]
+    fn empty_properties() {
+        let (properties, consumed) = parse_properties(&[0x00]).unwrap().unwrap();
+        assert_eq!(1, consumed);
+        assert_eq!(0, properties.count());
+    }
+
+    #[test]
+    fn mixed_properties() {
+        let mut bytes = Vec::new();
+        bytes.push(0x01); // Payload Format Indicator
+        bytes.push(0x11); // value
+        bytes.push(0x23); // Topic Alias
+        bytes.extend_from_slice(&7u16.to_be_bytes());
+        bytes.push(0x26); // User Property
+        bytes.extend_from_slice(&3u16.to_be_bytes());
+        bytes.extend_from_slice(b"key");
+        bytes.extend_from_slice(&3u16.to_be_bytes());
+        bytes.extend_from_slice(b"val");
+
+        let mut buf = Vec::new();
+        buf.push(bytes.len() as u8);
+        buf.extend_from_slice(&bytes);
+
+        let (properties, consumed) = parse_properties(&buf).unwrap().unwrap();
+        assert_eq!(buf.len(), consumed);
+
+        let parsed: Vec<_> = properties.collect::<Result<_>>().unwrap();
+        assert_eq!(
+            vec![
+                Property::PayloadFormatIndicator(0x11),
+                Property::TopicAlias(7),
+                Property::UserProperty("key", "val"),
+            ],
+            parsed
+        );
+    }
+
+    #[test]
+    fn unknown_identifier() {
+        let buf = [0x01, 0xFF];
+        assert_eq!(
+            Some(Err(Error::InvalidProperty)),
+            parse_properties(&buf).unwrap().unwrap().0.next()
+        );
+    }
+
+    #[test]
+    fn truncated_value() {
+        // Topic Alias (Two Byte Integer) declares only 2 bytes of property
+        // block, but its value needs 2 bytes after the identifier.
+        let buf = [0x02, 0x23, 0x00];
+        assert_eq!(
+            Some(Err(Error::InvalidProperty)),
+            parse_properties(&buf).unwrap().unwrap().0.next()
+        );
+    }
+}
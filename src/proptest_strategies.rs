@@ -0,0 +1,174 @@
+use proptest::prelude::*;
+use rayon::prelude::*;
+
+use crate::connack::{Connack, ConnectReturnCode};
+use crate::connect::{Connect, Will};
+use crate::puback::Puback;
+use crate::pubcomp::Pubcomp;
+use crate::publish::Publish;
+use crate::pubrec::Pubrec;
+use crate::pubrel::Pubrel;
+use crate::unsuback::Unsuback;
+use crate::{parse, Packet, PacketId, QoS, Status};
+
+/// Valid MQTT UTF-8 strings for these tests: short, printable, and never
+/// containing the NUL code point `parse_string` rejects.
+fn valid_string() -> impl Strategy<Value = String> {
+    "[a-zA-Z0-9/]{0,16}"
+}
+
+fn qos_strategy() -> impl Strategy<Value = QoS> {
+    prop_oneof![
+        Just(QoS::AtMostOnce),
+        Just(QoS::AtLeastOnce),
+        Just(QoS::ExactlyOnce),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn round_trip_qos(qos in qos_strategy()) {
+        prop_assert_eq!(qos, QoS::from_u8(qos.to_u8()).unwrap());
+    }
+
+    #[test]
+    fn round_trip_connack(session_present: bool, return_code_num in 0u8..=5) {
+        let return_code = ConnectReturnCode::from_u8(return_code_num).unwrap();
+        let connack = Connack { session_present, return_code, properties: &[] };
+
+        let mut buf = [0u8; 2];
+        let written = connack.encode(&mut buf).unwrap().unwrap();
+        let (parsed, consumed) = Connack::parse(&buf[..written]).unwrap().unwrap();
+
+        prop_assert_eq!(connack, parsed);
+        prop_assert_eq!(written, consumed);
+    }
+
+    #[test]
+    fn round_trip_connect(
+        protocol_level: u8,
+        clean_session: bool,
+        keep_alive: u16,
+        client_id in valid_string(),
+        has_will: bool,
+        will_qos in qos_strategy(),
+        will_retain: bool,
+        will_topic in valid_string(),
+        will_message in prop::collection::vec(any::<u8>(), 0..16),
+        has_username: bool,
+        password in prop::collection::vec(any::<u8>(), 0..16),
+    ) {
+        let will = if has_will {
+            Some(Will { qos: will_qos, retain: will_retain, topic: will_topic.as_str(), message: &will_message })
+        } else {
+            None
+        };
+        let username = if has_username { Some("user") } else { None };
+        // MQTT-3.1.2-22: a Password Flag without a User Name Flag is invalid,
+        // so only generate a password alongside a username.
+        let password = if has_username { Some(password.as_slice()) } else { None };
+
+        let connect = Connect {
+            protocol_level,
+            clean_session,
+            keep_alive,
+            client_id: client_id.as_str(),
+            will,
+            username,
+            password,
+        };
+
+        let mut buf = [0u8; 256];
+        let written = connect.encode(&mut buf).unwrap().unwrap();
+        let (parsed, consumed) = Connect::parse(&buf[..written]).unwrap().unwrap();
+
+        prop_assert_eq!(connect, parsed);
+        prop_assert_eq!(written, consumed);
+    }
+
+    #[test]
+    fn round_trip_publish(
+        dup: bool,
+        qos in qos_strategy(),
+        retain: bool,
+        topic_name in valid_string(),
+        packet_id: PacketId,
+        payload in prop::collection::vec(any::<u8>(), 0..64),
+    ) {
+        // A packet id is only present on the wire when QoS > 0.
+        let packet_id = if qos == QoS::AtMostOnce { None } else { Some(packet_id) };
+
+        let publish = Publish {
+            dup,
+            qos,
+            retain,
+            topic_name: topic_name.as_str(),
+            packet_id,
+            payload: &payload,
+        };
+
+        let mut buf = [0u8; 128];
+        let written = publish.encode(&mut buf).unwrap().unwrap();
+        let (parsed, consumed) = Publish::parse(publish.flags(), &buf[..written]).unwrap().unwrap();
+
+        prop_assert_eq!(publish, parsed);
+        prop_assert_eq!(written, consumed);
+    }
+
+    #[test]
+    fn truncated_publish_is_always_partial(
+        dup: bool,
+        qos in qos_strategy(),
+        retain: bool,
+        topic_name in valid_string(),
+        packet_id: PacketId,
+        payload in prop::collection::vec(any::<u8>(), 0..64),
+    ) {
+        let packet_id = if qos == QoS::AtMostOnce { None } else { Some(packet_id) };
+        let packet = Packet::Publish(Publish {
+            dup,
+            qos,
+            retain,
+            topic_name: topic_name.as_str(),
+            packet_id,
+            payload: &payload,
+        });
+
+        let mut buf = [0u8; 128];
+        let written = crate::packet::encode(&packet, &mut buf).unwrap().unwrap();
+
+        (0..written).into_par_iter().for_each(|prefix_len| {
+            assert_eq!(Status::Partial, parse(&buf[..prefix_len]).unwrap());
+        });
+
+        let (parsed, consumed) = parse(&buf[..written]).unwrap().unwrap();
+        prop_assert_eq!(packet, parsed);
+        prop_assert_eq!(written, consumed);
+    }
+}
+
+/// Generates a property test named `$name` that round-trips `$ty`, shared by
+/// the packet types that are nothing more than a `packet_id: PacketId`.
+macro_rules! round_trip_packet_id {
+    ($name:ident, $ty:ident) => {
+        proptest! {
+            #[test]
+            fn $name(packet_id: PacketId) {
+                let packet = $ty { packet_id };
+
+                let mut buf = [0u8; 4];
+                let written = packet.encode(&mut buf).unwrap().unwrap();
+                let (parsed, consumed) = $ty::parse(&buf[..written]).unwrap().unwrap();
+
+                prop_assert_eq!(packet, parsed);
+                prop_assert_eq!(written, consumed);
+            }
+        }
+    };
+}
+
+round_trip_packet_id!(round_trip_puback, Puback);
+round_trip_packet_id!(round_trip_pubrec, Pubrec);
+round_trip_packet_id!(round_trip_pubrel, Pubrel);
+round_trip_packet_id!(round_trip_pubcomp, Pubcomp);
+round_trip_packet_id!(round_trip_unsuback, Unsuback);
@@ -0,0 +1,63 @@
+use byteorder::{BigEndian, ByteOrder};
+
+use crate::{Error, PacketId, Result, Status};
+
+/// A parsed PUBACK packet (MQTT 3.1.1, section 3.4): the QoS 1
+/// acknowledgement of a PUBLISH.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Puback {
+    pub packet_id: PacketId,
+}
+
+impl Puback {
+    pub fn parse(bytes: &[u8]) -> Result<Status<(Puback, usize)>> {
+        if bytes.len() < 2 {
+            return Err(Error::MalformedPacket);
+        }
+
+        Ok(Status::Complete((
+            Puback {
+                packet_id: BigEndian::read_u16(bytes),
+            },
+            2,
+        )))
+    }
+
+    /// Encodes the PUBACK variable header, the mirror image of
+    /// [`Puback::parse`].
+    pub fn encode(&self, buf: &mut [u8]) -> Result<Status<usize>> {
+        if buf.len() < 2 {
+            return Ok(Status::Partial);
+        }
+
+        BigEndian::write_u16(buf, self.packet_id);
+        Ok(Status::Complete(2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_buffer() {
+        assert_eq!(Err(Error::MalformedPacket), Puback::parse(&[0]));
+    }
+
+    #[test]
+    fn packet_id() {
+        let (puback, consumed) = Puback::parse(&[0x00, 0x2A]).unwrap().unwrap();
+        assert_eq!(42, puback.packet_id);
+        assert_eq!(2, consumed);
+    }
+
+    #[test]
+    fn round_trip() {
+        let puback = Puback { packet_id: 42 };
+        let mut buf = [0u8; 2];
+        let written = puback.encode(&mut buf).unwrap().unwrap();
+        let (parsed, consumed) = Puback::parse(&buf[..written]).unwrap().unwrap();
+        assert_eq!(puback, parsed);
+        assert_eq!(written, consumed);
+    }
+}
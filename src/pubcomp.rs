@@ -0,0 +1,63 @@
+use byteorder::{BigEndian, ByteOrder};
+
+use crate::{Error, PacketId, Result, Status};
+
+/// A parsed PUBCOMP packet (MQTT 3.1.1, section 3.7): completes the QoS 2
+/// publish handshake.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Pubcomp {
+    pub packet_id: PacketId,
+}
+
+impl Pubcomp {
+    pub fn parse(bytes: &[u8]) -> Result<Status<(Pubcomp, usize)>> {
+        if bytes.len() < 2 {
+            return Err(Error::MalformedPacket);
+        }
+
+        Ok(Status::Complete((
+            Pubcomp {
+                packet_id: BigEndian::read_u16(bytes),
+            },
+            2,
+        )))
+    }
+
+    /// Encodes the PUBCOMP variable header, the mirror image of
+    /// [`Pubcomp::parse`].
+    pub fn encode(&self, buf: &mut [u8]) -> Result<Status<usize>> {
+        if buf.len() < 2 {
+            return Ok(Status::Partial);
+        }
+
+        BigEndian::write_u16(buf, self.packet_id);
+        Ok(Status::Complete(2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_buffer() {
+        assert_eq!(Err(Error::MalformedPacket), Pubcomp::parse(&[0]));
+    }
+
+    #[test]
+    fn packet_id() {
+        let (pubcomp, consumed) = Pubcomp::parse(&[0x00, 0x2A]).unwrap().unwrap();
+        assert_eq!(42, pubcomp.packet_id);
+        assert_eq!(2, consumed);
+    }
+
+    #[test]
+    fn round_trip() {
+        let pubcomp = Pubcomp { packet_id: 42 };
+        let mut buf = [0u8; 2];
+        let written = pubcomp.encode(&mut buf).unwrap().unwrap();
+        let (parsed, consumed) = Pubcomp::parse(&buf[..written]).unwrap().unwrap();
+        assert_eq!(pubcomp, parsed);
+        assert_eq!(written, consumed);
+    }
+}
@@ -0,0 +1,163 @@
+use byteorder::{BigEndian, ByteOrder};
+
+use crate::{encode_string, parse_string, Error, PacketId, PacketTypeFlags, QoS, Result, Status};
+
+/// A parsed PUBLISH packet (MQTT 3.1.1, section 3.3).
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Publish<'a> {
+    pub dup: bool,
+    pub qos: QoS,
+    pub retain: bool,
+    pub topic_name: &'a str,
+    pub packet_id: Option<PacketId>,
+    pub payload: &'a [u8],
+}
+
+impl<'a> Publish<'a> {
+    /// Parses a PUBLISH packet's variable header and payload. `flags` is
+    /// the fixed header's flags nibble, which carries DUP, QoS and RETAIN
+    /// for this packet type.
+    pub fn parse(flags: PacketTypeFlags, bytes: &'a [u8]) -> Result<Status<(Publish<'a>, usize)>> {
+        let dup = flags & 0x08 != 0;
+        let qos = QoS::from_u8((flags & 0x06) >> 1)?;
+        let retain = flags & 0x01 != 0;
+
+        let mut pos = 0;
+
+        let (topic_name, len) = complete_or_err!(parse_string(bytes)?);
+        pos += len;
+
+        let packet_id = if qos == QoS::AtMostOnce {
+            None
+        } else {
+            if bytes.len() < pos + 2 {
+                return Err(Error::MalformedPacket);
+            }
+            let id = BigEndian::read_u16(&bytes[pos..]);
+            pos += 2;
+            Some(id)
+        };
+
+        let payload = &bytes[pos..];
+
+        Ok(Status::Complete((
+            Publish {
+                dup,
+                qos,
+                retain,
+                topic_name,
+                packet_id,
+                payload,
+            },
+            bytes.len(),
+        )))
+    }
+
+    /// The fixed header flags nibble (DUP, QoS, RETAIN) for this packet.
+    pub fn flags(&self) -> PacketTypeFlags {
+        let mut flags = self.qos.to_u8() << 1;
+        if self.dup {
+            flags |= 0x08;
+        }
+        if self.retain {
+            flags |= 0x01;
+        }
+        flags
+    }
+
+    /// Encodes the PUBLISH variable header and payload, the mirror image of
+    /// [`Publish::parse`]. DUP, QoS and RETAIN are carried in the fixed
+    /// header flags nibble (see [`Publish::flags`]), not written here.
+    pub fn encode(&self, buf: &mut [u8]) -> Result<Status<usize>> {
+        let mut pos = complete!(encode_string(self.topic_name, buf)?);
+
+        if let Some(packet_id) = self.packet_id {
+            if buf.len() < pos + 2 {
+                return Ok(Status::Partial);
+            }
+            BigEndian::write_u16(&mut buf[pos..], packet_id);
+            pos += 2;
+        }
+
+        if buf.len() < pos + self.payload.len() {
+            return Ok(Status::Partial);
+        }
+        buf[pos..pos + self.payload.len()].copy_from_slice(self.payload);
+        pos += self.payload.len();
+
+        Ok(Status::Complete(pos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn qos0_has_no_packet_id() {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&5u16.to_be_bytes());
+        bytes.extend_from_slice(b"topic");
+        bytes.extend_from_slice(b"hello");
+
+        let (publish, consumed) = Publish::parse(0x00, &bytes).unwrap().unwrap();
+        assert!(!publish.dup);
+        assert_eq!(QoS::AtMostOnce, publish.qos);
+        assert!(!publish.retain);
+        assert_eq!("topic", publish.topic_name);
+        assert_eq!(None, publish.packet_id);
+        assert_eq!(b"hello".as_ref(), publish.payload);
+        assert_eq!(bytes.len(), consumed);
+    }
+
+    #[test]
+    fn qos1_has_packet_id() {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&5u16.to_be_bytes());
+        bytes.extend_from_slice(b"topic");
+        bytes.extend_from_slice(&42u16.to_be_bytes());
+        bytes.extend_from_slice(b"hi");
+
+        // QoS 1, retain set
+        let (publish, consumed) = Publish::parse(0x03, &bytes).unwrap().unwrap();
+        assert_eq!(QoS::AtLeastOnce, publish.qos);
+        assert!(publish.retain);
+        assert_eq!(Some(42), publish.packet_id);
+        assert_eq!(b"hi".as_ref(), publish.payload);
+        assert_eq!(bytes.len(), consumed);
+    }
+
+    #[test]
+    fn partial_packet_id() {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&5u16.to_be_bytes());
+        bytes.extend_from_slice(b"topic");
+        bytes.push(0); // only 1 of the 2 packet id bytes
+
+        assert_eq!(
+            Err(Error::MalformedPacket),
+            Publish::parse(0x02, &bytes)
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let publish = Publish {
+            dup: true,
+            qos: QoS::ExactlyOnce,
+            retain: false,
+            topic_name: "a/b",
+            packet_id: Some(7),
+            payload: b"payload",
+        };
+
+        let mut buf = [0u8; 32];
+        let written = publish.encode(&mut buf).unwrap().unwrap();
+        let (parsed, consumed) = Publish::parse(publish.flags(), &buf[..written])
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(publish, parsed);
+        assert_eq!(written, consumed);
+    }
+}
@@ -0,0 +1,63 @@
+use byteorder::{BigEndian, ByteOrder};
+
+use crate::{Error, PacketId, Result, Status};
+
+/// A parsed PUBREC packet (MQTT 3.1.1, section 3.5): the first half of the
+/// QoS 2 publish handshake.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Pubrec {
+    pub packet_id: PacketId,
+}
+
+impl Pubrec {
+    pub fn parse(bytes: &[u8]) -> Result<Status<(Pubrec, usize)>> {
+        if bytes.len() < 2 {
+            return Err(Error::MalformedPacket);
+        }
+
+        Ok(Status::Complete((
+            Pubrec {
+                packet_id: BigEndian::read_u16(bytes),
+            },
+            2,
+        )))
+    }
+
+    /// Encodes the PUBREC variable header, the mirror image of
+    /// [`Pubrec::parse`].
+    pub fn encode(&self, buf: &mut [u8]) -> Result<Status<usize>> {
+        if buf.len() < 2 {
+            return Ok(Status::Partial);
+        }
+
+        BigEndian::write_u16(buf, self.packet_id);
+        Ok(Status::Complete(2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_buffer() {
+        assert_eq!(Err(Error::MalformedPacket), Pubrec::parse(&[0]));
+    }
+
+    #[test]
+    fn packet_id() {
+        let (pubrec, consumed) = Pubrec::parse(&[0x00, 0x2A]).unwrap().unwrap();
+        assert_eq!(42, pubrec.packet_id);
+        assert_eq!(2, consumed);
+    }
+
+    #[test]
+    fn round_trip() {
+        let pubrec = Pubrec { packet_id: 42 };
+        let mut buf = [0u8; 2];
+        let written = pubrec.encode(&mut buf).unwrap().unwrap();
+        let (parsed, consumed) = Pubrec::parse(&buf[..written]).unwrap().unwrap();
+        assert_eq!(pubrec, parsed);
+        assert_eq!(written, consumed);
+    }
+}
@@ -0,0 +1,63 @@
+use byteorder::{BigEndian, ByteOrder};
+
+use crate::{Error, PacketId, Result, Status};
+
+/// A parsed PUBREL packet (MQTT 3.1.1, section 3.6): the second half of the
+/// QoS 2 publish handshake.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Pubrel {
+    pub packet_id: PacketId,
+}
+
+impl Pubrel {
+    pub fn parse(bytes: &[u8]) -> Result<Status<(Pubrel, usize)>> {
+        if bytes.len() < 2 {
+            return Err(Error::MalformedPacket);
+        }
+
+        Ok(Status::Complete((
+            Pubrel {
+                packet_id: BigEndian::read_u16(bytes),
+            },
+            2,
+        )))
+    }
+
+    /// Encodes the PUBREL variable header, the mirror image of
+    /// [`Pubrel::parse`].
+    pub fn encode(&self, buf: &mut [u8]) -> Result<Status<usize>> {
+        if buf.len() < 2 {
+            return Ok(Status::Partial);
+        }
+
+        BigEndian::write_u16(buf, self.packet_id);
+        Ok(Status::Complete(2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_buffer() {
+        assert_eq!(Err(Error::MalformedPacket), Pubrel::parse(&[0]));
+    }
+
+    #[test]
+    fn packet_id() {
+        let (pubrel, consumed) = Pubrel::parse(&[0x00, 0x2A]).unwrap().unwrap();
+        assert_eq!(42, pubrel.packet_id);
+        assert_eq!(2, consumed);
+    }
+
+    #[test]
+    fn round_trip() {
+        let pubrel = Pubrel { packet_id: 42 };
+        let mut buf = [0u8; 2];
+        let written = pubrel.encode(&mut buf).unwrap().unwrap();
+        let (parsed, consumed) = Pubrel::parse(&buf[..written]).unwrap().unwrap();
+        assert_eq!(pubrel, parsed);
+        assert_eq!(written, consumed);
+    }
+}
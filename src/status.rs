@@ -0,0 +1,56 @@
+/// The outcome of attempting to parse a value out of a byte slice that may
+/// not yet hold enough data.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Status<T> {
+    /// `bytes` held everything needed to produce a `T`.
+    Complete(T),
+
+    /// `bytes` was too short; the caller should read more data and retry.
+    Partial,
+}
+
+impl<T> Status<T> {
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> Status<U> {
+        match self {
+            Status::Complete(val) => Status::Complete(f(val)),
+            Status::Partial => Status::Partial,
+        }
+    }
+
+    /// Unwraps a `Complete` value, panicking if the status is `Partial`.
+    pub fn unwrap(self) -> T {
+        match self {
+            Status::Complete(val) => val,
+            Status::Partial => panic!("unwrap called on Status::Partial"),
+        }
+    }
+}
+
+/// Unwraps a `Status<T>` to a `T`, propagating `Status::Partial` out of the
+/// enclosing function as `Ok(Status::Partial)`. Meant for chaining the
+/// `parse_*` helpers together inside functions that themselves return
+/// `Result<Status<_>>`.
+#[macro_export]
+macro_rules! complete {
+    ($e:expr) => {
+        match $e {
+            $crate::Status::Complete(val) => val,
+            $crate::Status::Partial => return Ok($crate::Status::Partial),
+        }
+    };
+}
+
+/// Like [`complete!`], but for contexts where `bytes` has already been
+/// sliced to a packet's declared Remaining Length and so can never grow:
+/// a `Status::Partial` there doesn't mean "read more and retry", it means
+/// the packet lied about its own length. Propagates that as
+/// `Error::MalformedPacket` instead of `Status::Partial`.
+#[macro_export]
+macro_rules! complete_or_err {
+    ($e:expr) => {
+        match $e {
+            $crate::Status::Complete(val) => val,
+            $crate::Status::Partial => return Err($crate::Error::MalformedPacket),
+        }
+    };
+}
@@ -0,0 +1,130 @@
+use byteorder::{BigEndian, ByteOrder};
+
+use crate::{Error, PacketId, QoS, Result, Status};
+
+/// The per-filter outcome of a SUBSCRIBE request, as reported in SUBACK.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum SubscribeReturnCode {
+    Success(QoS),
+    Failure,
+}
+
+impl SubscribeReturnCode {
+    fn from_u8(code: u8) -> Result<SubscribeReturnCode> {
+        match code {
+            0..=2 => Ok(SubscribeReturnCode::Success(QoS::from_u8(code)?)),
+            0x80 => Ok(SubscribeReturnCode::Failure),
+            _ => Err(Error::InvalidSubackReturnCode),
+        }
+    }
+}
+
+/// A parsed SUBACK packet (MQTT 3.1.1, section 3.9).
+///
+/// As with [`crate::subscribe::Subscribe`], the return codes are exposed as
+/// a zero-copy iterator rather than a `Vec`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Suback<'a> {
+    pub packet_id: PacketId,
+    payload: &'a [u8],
+}
+
+impl<'a> Suback<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<Status<(Suback<'a>, usize)>> {
+        if bytes.len() < 2 {
+            return Err(Error::MalformedPacket);
+        }
+
+        let packet_id = BigEndian::read_u16(bytes);
+        let payload = &bytes[2..];
+
+        Ok(Status::Complete((Suback { packet_id, payload }, bytes.len())))
+    }
+
+    pub fn return_codes(&self) -> ReturnCodes<'a> {
+        ReturnCodes { bytes: self.payload }
+    }
+
+    /// Encodes the SUBACK variable header and payload, the mirror image of
+    /// [`Suback::parse`].
+    pub fn encode(&self, buf: &mut [u8]) -> Result<Status<usize>> {
+        if buf.len() < 2 + self.payload.len() {
+            return Ok(Status::Partial);
+        }
+
+        BigEndian::write_u16(buf, self.packet_id);
+        buf[2..2 + self.payload.len()].copy_from_slice(self.payload);
+
+        Ok(Status::Complete(2 + self.payload.len()))
+    }
+}
+
+/// Iterates the Return Codes in a SUBACK payload.
+pub struct ReturnCodes<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Iterator for ReturnCodes<'a> {
+    type Item = Result<SubscribeReturnCode>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bytes.is_empty() {
+            return None;
+        }
+
+        let code = self.bytes[0];
+        self.bytes = &self.bytes[1..];
+        Some(SubscribeReturnCode::from_u8(code))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_buffer() {
+        assert_eq!(Err(Error::MalformedPacket), Suback::parse(&[0]));
+    }
+
+    #[test]
+    fn return_codes() {
+        let bytes = [0x00, 0x2A, 0x00, 0x01, 0x80];
+        let (suback, consumed) = Suback::parse(&bytes).unwrap().unwrap();
+        assert_eq!(42, suback.packet_id);
+        assert_eq!(bytes.len(), consumed);
+
+        let codes: Vec<_> = suback.return_codes().collect::<Result<_>>().unwrap();
+        assert_eq!(
+            vec![
+                SubscribeReturnCode::Success(QoS::AtMostOnce),
+                SubscribeReturnCode::Success(QoS::AtLeastOnce),
+                SubscribeReturnCode::Failure,
+            ],
+            codes
+        );
+    }
+
+    #[test]
+    fn invalid_return_code() {
+        let bytes = [0x00, 0x2A, 0x03];
+        let (suback, _) = Suback::parse(&bytes).unwrap().unwrap();
+        assert_eq!(
+            Some(Err(Error::InvalidSubackReturnCode)),
+            suback.return_codes().next()
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let bytes = [0x00, 0x2A, 0x00, 0x01, 0x80];
+        let (suback, _) = Suback::parse(&bytes).unwrap().unwrap();
+
+        let mut buf = [0u8; 8];
+        let written = suback.encode(&mut buf).unwrap().unwrap();
+        let (parsed, consumed) = Suback::parse(&buf[..written]).unwrap().unwrap();
+
+        assert_eq!(suback, parsed);
+        assert_eq!(written, consumed);
+    }
+}
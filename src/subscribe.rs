@@ -0,0 +1,150 @@
+use byteorder::{BigEndian, ByteOrder};
+
+use crate::{parse_string, Error, PacketId, QoS, Result, Status};
+
+/// A parsed SUBSCRIBE packet (MQTT 3.1.1, section 3.8).
+///
+/// The topic filter list is kept as a borrowed, zero-copy iterator rather
+/// than a `Vec` so the parser stays usable in `no_std` contexts; call
+/// [`Subscribe::topic_filters`] to walk it.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Subscribe<'a> {
+    pub packet_id: PacketId,
+    payload: &'a [u8],
+}
+
+impl<'a> Subscribe<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<Status<(Subscribe<'a>, usize)>> {
+        if bytes.len() < 2 {
+            return Err(Error::MalformedPacket);
+        }
+
+        let packet_id = BigEndian::read_u16(bytes);
+        let payload = &bytes[2..];
+
+        Ok(Status::Complete((
+            Subscribe { packet_id, payload },
+            bytes.len(),
+        )))
+    }
+
+    pub fn topic_filters(&self) -> TopicFilters<'a> {
+        TopicFilters { bytes: self.payload }
+    }
+
+    /// Encodes the SUBSCRIBE variable header and payload, the mirror image
+    /// of [`Subscribe::parse`].
+    pub fn encode(&self, buf: &mut [u8]) -> Result<Status<usize>> {
+        if buf.len() < 2 + self.payload.len() {
+            return Ok(Status::Partial);
+        }
+
+        BigEndian::write_u16(buf, self.packet_id);
+        buf[2..2 + self.payload.len()].copy_from_slice(self.payload);
+
+        Ok(Status::Complete(2 + self.payload.len()))
+    }
+}
+
+/// Iterates the (Topic Filter, Requested QoS) pairs in a SUBSCRIBE payload.
+pub struct TopicFilters<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Iterator for TopicFilters<'a> {
+    type Item = Result<(&'a str, QoS)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bytes.is_empty() {
+            return None;
+        }
+
+        let (topic, len) = match parse_string(self.bytes) {
+            Ok(Status::Complete(pair)) => pair,
+            Ok(Status::Partial) => {
+                self.bytes = &[];
+                return Some(Err(Error::MalformedPacket));
+            }
+            Err(err) => {
+                self.bytes = &[];
+                return Some(Err(err));
+            }
+        };
+
+        if self.bytes.len() < len + 1 {
+            self.bytes = &[];
+            return Some(Err(Error::MalformedPacket));
+        }
+
+        let qos = QoS::from_u8(self.bytes[len]);
+        self.bytes = &self.bytes[len + 1..];
+
+        Some(qos.map(|qos| (topic, qos)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_buffer() {
+        assert_eq!(Err(Error::MalformedPacket), Subscribe::parse(&[0]));
+    }
+
+    #[test]
+    fn topic_filters() {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&7u16.to_be_bytes());
+        bytes.extend_from_slice(&5u16.to_be_bytes());
+        bytes.extend_from_slice(b"a/b/c");
+        bytes.push(0x01);
+        bytes.extend_from_slice(&3u16.to_be_bytes());
+        bytes.extend_from_slice(b"d/e");
+        bytes.push(0x02);
+
+        let (subscribe, consumed) = Subscribe::parse(&bytes).unwrap().unwrap();
+        assert_eq!(7, subscribe.packet_id);
+        assert_eq!(bytes.len(), consumed);
+
+        let filters: Vec<_> = subscribe
+            .topic_filters()
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(
+            vec![("a/b/c", QoS::AtLeastOnce), ("d/e", QoS::ExactlyOnce)],
+            filters
+        );
+    }
+
+    #[test]
+    fn truncated_topic_filter() {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&7u16.to_be_bytes());
+        bytes.extend_from_slice(&5u16.to_be_bytes()); // claims 5 bytes, body has none
+
+        let (subscribe, _) = Subscribe::parse(&bytes).unwrap().unwrap();
+        assert_eq!(
+            Some(Err(Error::MalformedPacket)),
+            subscribe.topic_filters().next()
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&5u16.to_be_bytes());
+        bytes.extend_from_slice(&5u16.to_be_bytes());
+        bytes.extend_from_slice(b"a/b/c");
+        bytes.push(0x01);
+
+        let (subscribe, _) = Subscribe::parse(&bytes).unwrap().unwrap();
+
+        let mut buf = [0u8; 32];
+        let written = subscribe.encode(&mut buf).unwrap().unwrap();
+        let (parsed, consumed) = Subscribe::parse(&buf[..written]).unwrap().unwrap();
+
+        assert_eq!(subscribe, parsed);
+        assert_eq!(written, consumed);
+    }
+}
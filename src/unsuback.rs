@@ -0,0 +1,62 @@
+use byteorder::{BigEndian, ByteOrder};
+
+use crate::{Error, PacketId, Result, Status};
+
+/// A parsed UNSUBACK packet (MQTT 3.1.1, section 3.11).
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Unsuback {
+    pub packet_id: PacketId,
+}
+
+impl Unsuback {
+    pub fn parse(bytes: &[u8]) -> Result<Status<(Unsuback, usize)>> {
+        if bytes.len() < 2 {
+            return Err(Error::MalformedPacket);
+        }
+
+        Ok(Status::Complete((
+            Unsuback {
+                packet_id: BigEndian::read_u16(bytes),
+            },
+            2,
+        )))
+    }
+
+    /// Encodes the UNSUBACK variable header, the mirror image of
+    /// [`Unsuback::parse`].
+    pub fn encode(&self, buf: &mut [u8]) -> Result<Status<usize>> {
+        if buf.len() < 2 {
+            return Ok(Status::Partial);
+        }
+
+        BigEndian::write_u16(buf, self.packet_id);
+        Ok(Status::Complete(2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_buffer() {
+        assert_eq!(Err(Error::MalformedPacket), Unsuback::parse(&[0]));
+    }
+
+    #[test]
+    fn packet_id() {
+        let (unsuback, consumed) = Unsuback::parse(&[0x00, 0x2A]).unwrap().unwrap();
+        assert_eq!(42, unsuback.packet_id);
+        assert_eq!(2, consumed);
+    }
+
+    #[test]
+    fn round_trip() {
+        let unsuback = Unsuback { packet_id: 42 };
+        let mut buf = [0u8; 2];
+        let written = unsuback.encode(&mut buf).unwrap().unwrap();
+        let (parsed, consumed) = Unsuback::parse(&buf[..written]).unwrap().unwrap();
+        assert_eq!(unsuback, parsed);
+        assert_eq!(written, consumed);
+    }
+}
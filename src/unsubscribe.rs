@@ -0,0 +1,136 @@
+use byteorder::{BigEndian, ByteOrder};
+
+use crate::{parse_string, Error, PacketId, Result, Status};
+
+/// A parsed UNSUBSCRIBE packet (MQTT 3.1.1, section 3.10).
+///
+/// As with [`crate::subscribe::Subscribe`], the topic filter list is
+/// exposed as a zero-copy iterator rather than a `Vec`.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Unsubscribe<'a> {
+    pub packet_id: PacketId,
+    payload: &'a [u8],
+}
+
+impl<'a> Unsubscribe<'a> {
+    pub fn parse(bytes: &'a [u8]) -> Result<Status<(Unsubscribe<'a>, usize)>> {
+        if bytes.len() < 2 {
+            return Err(Error::MalformedPacket);
+        }
+
+        let packet_id = BigEndian::read_u16(bytes);
+        let payload = &bytes[2..];
+
+        Ok(Status::Complete((
+            Unsubscribe { packet_id, payload },
+            bytes.len(),
+        )))
+    }
+
+    pub fn topic_filters(&self) -> TopicFilters<'a> {
+        TopicFilters { bytes: self.payload }
+    }
+
+    /// Encodes the UNSUBSCRIBE variable header and payload, the mirror
+    /// image of [`Unsubscribe::parse`].
+    pub fn encode(&self, buf: &mut [u8]) -> Result<Status<usize>> {
+        if buf.len() < 2 + self.payload.len() {
+            return Ok(Status::Partial);
+        }
+
+        BigEndian::write_u16(buf, self.packet_id);
+        buf[2..2 + self.payload.len()].copy_from_slice(self.payload);
+
+        Ok(Status::Complete(2 + self.payload.len()))
+    }
+}
+
+/// Iterates the Topic Filters in an UNSUBSCRIBE payload.
+pub struct TopicFilters<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Iterator for TopicFilters<'a> {
+    type Item = Result<&'a str>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bytes.is_empty() {
+            return None;
+        }
+
+        match parse_string(self.bytes) {
+            Ok(Status::Complete((topic, len))) => {
+                self.bytes = &self.bytes[len..];
+                Some(Ok(topic))
+            }
+            Ok(Status::Partial) => {
+                self.bytes = &[];
+                Some(Err(Error::MalformedPacket))
+            }
+            Err(err) => {
+                self.bytes = &[];
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_buffer() {
+        assert_eq!(Err(Error::MalformedPacket), Unsubscribe::parse(&[0]));
+    }
+
+    #[test]
+    fn topic_filters() {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&7u16.to_be_bytes());
+        bytes.extend_from_slice(&5u16.to_be_bytes());
+        bytes.extend_from_slice(b"a/b/c");
+        bytes.extend_from_slice(&3u16.to_be_bytes());
+        bytes.extend_from_slice(b"d/e");
+
+        let (unsubscribe, consumed) = Unsubscribe::parse(&bytes).unwrap().unwrap();
+        assert_eq!(7, unsubscribe.packet_id);
+        assert_eq!(bytes.len(), consumed);
+
+        let filters: Vec<_> = unsubscribe
+            .topic_filters()
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(vec!["a/b/c", "d/e"], filters);
+    }
+
+    #[test]
+    fn truncated_topic_filter() {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&7u16.to_be_bytes());
+        bytes.extend_from_slice(&5u16.to_be_bytes()); // claims 5 bytes, body has none
+
+        let (unsubscribe, _) = Unsubscribe::parse(&bytes).unwrap().unwrap();
+        assert_eq!(
+            Some(Err(Error::MalformedPacket)),
+            unsubscribe.topic_filters().next()
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&7u16.to_be_bytes());
+        bytes.extend_from_slice(&5u16.to_be_bytes());
+        bytes.extend_from_slice(b"a/b/c");
+
+        let (unsubscribe, _) = Unsubscribe::parse(&bytes).unwrap().unwrap();
+
+        let mut buf = [0u8; 16];
+        let written = unsubscribe.encode(&mut buf).unwrap().unwrap();
+        let (parsed, consumed) = Unsubscribe::parse(&buf[..written]).unwrap().unwrap();
+
+        assert_eq!(unsubscribe, parsed);
+        assert_eq!(written, consumed);
+    }
+}